@@ -0,0 +1,157 @@
+//! CDI amounts as integer base units.
+//!
+//! Every value on the ledger is a count of indivisible base units, never an
+//! `f64`. One CDI is 10^8 base units — the same 1e-8 floor the halving
+//! schedule already clamped to — so wallets and nodes agree on amounts
+//! bit-for-bit. Floating point only ever appears at the display edge, via the
+//! parse/format helpers below.
+
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::prelude::*;
+
+/// Decimal places in the canonical CDI denomination: 1 CDI = 10^DECIMALS base units.
+pub const DECIMALS: u32 = 8;
+
+/// Number of base units in one whole CDI.
+pub const BASE_UNITS_PER_CDI: u64 = 100_000_000;
+
+/// An amount of CDI, stored as a count of base units.
+///
+/// Serializes transparently as the underlying `u64` so a `SignedTransaction`
+/// carries the exact integer rather than a float rendering of it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct CdiAmount(u64);
+
+impl CdiAmount {
+    /// Wrap a raw count of base units.
+    pub fn from_base_units(units: u64) -> CdiAmount {
+        CdiAmount(units)
+    }
+
+    /// Whole CDI → base units (e.g. 50 CDI).
+    pub fn from_cdi(whole: u64) -> CdiAmount {
+        CdiAmount(whole.saturating_mul(BASE_UNITS_PER_CDI))
+    }
+
+    /// The raw base-unit count.
+    pub fn base_units(&self) -> u64 {
+        self.0
+    }
+
+    /// Parse a denominated decimal string (e.g. "10.5") into base units, given
+    /// the denomination's decimal places. Rejects more fractional digits than
+    /// the denomination allows rather than silently truncating.
+    pub fn parse(s: &str, decimals: u32) -> Result<CdiAmount, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("empty amount".to_string());
+        }
+        let (int_part, frac_part) = match s.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (s, ""),
+        };
+        if frac_part.len() as u32 > decimals {
+            return Err(format!("too many fractional digits (max {})", decimals));
+        }
+        if !int_part.chars().all(|c| c.is_ascii_digit())
+            || !frac_part.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(format!("invalid amount: {}", s));
+        }
+        let scale = 10u64.pow(decimals);
+        let whole: u64 = if int_part.is_empty() {
+            0
+        } else {
+            int_part.parse().map_err(|_| format!("amount too large: {}", s))?
+        };
+        // Right-pad the fraction to `decimals` digits, then parse.
+        let mut frac_digits = frac_part.to_string();
+        while (frac_digits.len() as u32) < decimals {
+            frac_digits.push('0');
+        }
+        let frac: u64 = if frac_digits.is_empty() {
+            0
+        } else {
+            frac_digits.parse().map_err(|_| format!("invalid amount: {}", s))?
+        };
+        let units = whole
+            .checked_mul(scale)
+            .and_then(|w| w.checked_add(frac))
+            .ok_or_else(|| format!("amount too large: {}", s))?;
+        Ok(CdiAmount(units))
+    }
+
+    /// Format base units as a denominated decimal string, trimming trailing
+    /// fractional zeros the way a wallet UI displays balances.
+    pub fn format(&self, decimals: u32) -> String {
+        let scale = 10u64.pow(decimals);
+        let whole = self.0 / scale;
+        let frac = self.0 % scale;
+        if frac == 0 {
+            return whole.to_string();
+        }
+        let mut frac_str = format!("{:0width$}", frac, width = decimals as usize);
+        while frac_str.ends_with('0') {
+            frac_str.pop();
+        }
+        format!("{}.{}", whole, frac_str)
+    }
+}
+
+// ── WASM bindings ─────────────────────────────────────────────────────
+
+/// Parse a denominated decimal string into base units.
+#[wasm_bindgen(js_name = "parseCdi")]
+pub fn parse_cdi(amount: &str) -> Result<u64, JsValue> {
+    CdiAmount::parse(amount, DECIMALS)
+        .map(|a| a.base_units())
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Format base units as a denominated decimal string.
+#[wasm_bindgen(js_name = "formatCdi")]
+pub fn format_cdi(base_units: u64) -> String {
+    CdiAmount::from_base_units(base_units).format(DECIMALS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_cdi() {
+        assert_eq!(CdiAmount::from_cdi(1).base_units(), 100_000_000);
+        assert_eq!(CdiAmount::from_cdi(50).base_units(), 5_000_000_000);
+    }
+
+    #[test]
+    fn test_parse_whole_and_fraction() {
+        assert_eq!(CdiAmount::parse("1", DECIMALS).unwrap().base_units(), 100_000_000);
+        assert_eq!(CdiAmount::parse("10.5", DECIMALS).unwrap().base_units(), 1_050_000_000);
+        assert_eq!(CdiAmount::parse("0.00000001", DECIMALS).unwrap().base_units(), 1);
+        assert_eq!(CdiAmount::parse(".5", DECIMALS).unwrap().base_units(), 50_000_000);
+    }
+
+    #[test]
+    fn test_parse_rejects_excess_precision() {
+        assert!(CdiAmount::parse("1.000000001", DECIMALS).is_err());
+        assert!(CdiAmount::parse("abc", DECIMALS).is_err());
+        assert!(CdiAmount::parse("", DECIMALS).is_err());
+    }
+
+    #[test]
+    fn test_format_trims_zeros() {
+        assert_eq!(CdiAmount::from_base_units(100_000_000).format(DECIMALS), "1");
+        assert_eq!(CdiAmount::from_base_units(1_050_000_000).format(DECIMALS), "10.5");
+        assert_eq!(CdiAmount::from_base_units(1).format(DECIMALS), "0.00000001");
+    }
+
+    #[test]
+    fn test_parse_format_roundtrip() {
+        for s in &["0", "1", "10.5", "21000000", "0.12345678"] {
+            let a = CdiAmount::parse(s, DECIMALS).unwrap();
+            assert_eq!(&a.format(DECIMALS), s);
+        }
+    }
+}
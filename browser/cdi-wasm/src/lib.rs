@@ -1,6 +1,8 @@
 pub mod wallet;
 pub mod signing;
 pub mod tokenomics;
+pub mod amount;
+pub mod oracle;
 
 use wasm_bindgen::prelude::*;
 
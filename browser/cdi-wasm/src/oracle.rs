@@ -0,0 +1,296 @@
+//! Oracle-attested conditional payouts.
+//!
+//! An inference's outcome — a quality score, latency bucket, benchmark result —
+//! is attested by a signed oracle message, and the fee split is chosen from a
+//! payout curve keyed on that numeric outcome. Rather than pre-signing one
+//! conditional transaction per possible value, a contiguous outcome interval is
+//! covered by the minimal set of base-`b` digit *prefixes* (see
+//! [`cover_interval`]): fully-covered sub-trees collapse to a single prefix and
+//! only the ragged edges are expanded, turning an `O(b^n)` interval into
+//! `O(n·b)` branches. This is the DLC/CFD digit-decomposition technique recast
+//! for CDI fee distribution.
+
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::prelude::*;
+
+use crate::signing::SignedTransaction;
+use crate::wallet::CdiWallet;
+
+/// A fixed-length prefix of an outcome's base-`b` digit expansion.
+///
+/// Matches every `num_digits`-digit outcome whose leading digits equal
+/// `digits`; an empty `digits` matches the whole domain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DigitPrefix {
+    pub digits: Vec<u8>,
+    pub base: u8,
+    pub num_digits: u8,
+}
+
+impl DigitPrefix {
+    /// Does this prefix cover `outcome`?
+    pub fn matches(&self, outcome: u64) -> bool {
+        let full = value_to_digits(outcome, self.base, self.num_digits);
+        full.len() >= self.digits.len() && full[..self.digits.len()] == self.digits[..]
+    }
+}
+
+/// Expand `value` into its `num_digits` base-`base` digits, most significant first.
+fn value_to_digits(value: u64, base: u8, num_digits: u8) -> Vec<u8> {
+    let base = base as u64;
+    let mut digits = vec![0u8; num_digits as usize];
+    let mut v = value;
+    for i in (0..num_digits as usize).rev() {
+        digits[i] = (v % base) as u8;
+        v /= base;
+    }
+    digits
+}
+
+/// Cover the inclusive interval `[start, end]` with the minimal set of digit
+/// prefixes, over a domain of `num_digits` digits in `base`.
+///
+/// Descends from the most significant digit: a sub-tree that lies wholly inside
+/// the interval collapses to one prefix, so only the low/high edges are split
+/// digit-by-digit.
+pub fn cover_interval(start: u64, end: u64, base: u8, num_digits: u8) -> Vec<DigitPrefix> {
+    let mut out = Vec::new();
+    if start > end {
+        return out;
+    }
+    cover_node(start, end, base, num_digits, &mut Vec::new(), num_digits, 0, &mut out);
+    out
+}
+
+/// Recurse over the node covering `[node_lo, node_lo + base^level - 1]`.
+#[allow(clippy::too_many_arguments)]
+fn cover_node(
+    start: u64,
+    end: u64,
+    base: u8,
+    num_digits: u8,
+    prefix: &mut Vec<u8>,
+    level: u8,
+    node_lo: u64,
+    out: &mut Vec<DigitPrefix>,
+) {
+    let node_size = (base as u64).pow(level as u32);
+    let node_hi = node_lo + node_size - 1;
+    if end < node_lo || start > node_hi {
+        return; // no overlap
+    }
+    if start <= node_lo && node_hi <= end {
+        out.push(DigitPrefix { digits: prefix.clone(), base, num_digits });
+        return; // fully covered — collapse to one prefix
+    }
+    let child_size = node_size / base as u64;
+    for digit in 0..base {
+        prefix.push(digit);
+        cover_node(
+            start,
+            end,
+            base,
+            num_digits,
+            prefix,
+            level - 1,
+            node_lo + digit as u64 * child_size,
+            out,
+        );
+        prefix.pop();
+    }
+}
+
+// ── Oracle attestation ────────────────────────────────────────────────
+
+/// A signed oracle attestation of a numeric outcome.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OracleAttestation {
+    pub outcome: u64,
+    pub pub_key: String,   // oracle's hex public key
+    pub signature: String, // hex Ed25519 signature over the canonical bytes
+}
+
+/// Canonical bytes an oracle signs to attest `outcome`.
+fn attestation_bytes(outcome: u64) -> Vec<u8> {
+    format!("cdi-oracle:{}", outcome).into_bytes()
+}
+
+/// Produce an attestation for `outcome` signed by `oracle`.
+pub fn attest(oracle: &CdiWallet, outcome: u64) -> OracleAttestation {
+    OracleAttestation {
+        outcome,
+        pub_key: oracle.get_public_key_hex(),
+        signature: oracle.sign_data(&attestation_bytes(outcome)),
+    }
+}
+
+impl OracleAttestation {
+    /// Check the attestation signature against its declared public key.
+    pub fn verify(&self) -> bool {
+        CdiWallet::verify_with_public_key(&self.pub_key, &attestation_bytes(self.outcome), &self.signature)
+    }
+}
+
+// ── Conditional payout contract ───────────────────────────────────────
+
+/// One payout branch: the transaction released when the oracle attests an
+/// outcome matching `prefix`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PayoutBranch {
+    pub prefix: DigitPrefix,
+    pub tx: SignedTransaction,
+}
+
+/// A conditional-payout contract bound to a single oracle.
+///
+/// Built from a payout curve mapping outcome intervals to the transaction that
+/// should settle; each interval is expanded into digit-prefix branches.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OracleContract {
+    pub oracle_pub_key: String,
+    pub base: u8,
+    pub num_digits: u8,
+    pub branches: Vec<PayoutBranch>,
+}
+
+impl OracleContract {
+    /// Build a contract from a payout curve of `(start, end, tx)` entries.
+    pub fn from_curve(
+        oracle_pub_key: &str,
+        base: u8,
+        num_digits: u8,
+        curve: &[(u64, u64, SignedTransaction)],
+    ) -> OracleContract {
+        let mut branches = Vec::new();
+        for (start, end, tx) in curve {
+            for prefix in cover_interval(*start, *end, base, num_digits) {
+                branches.push(PayoutBranch { prefix, tx: tx.clone() });
+            }
+        }
+        OracleContract {
+            oracle_pub_key: oracle_pub_key.to_string(),
+            base,
+            num_digits,
+            branches,
+        }
+    }
+
+    /// Settle the contract against an attestation: verify the oracle signature
+    /// and return the transaction for the branch whose prefix matches the
+    /// attested outcome, if any.
+    pub fn settle(&self, attestation: &OracleAttestation) -> Option<&SignedTransaction> {
+        if attestation.pub_key != self.oracle_pub_key || !attestation.verify() {
+            return None;
+        }
+        self.branches
+            .iter()
+            .find(|b| b.prefix.matches(attestation.outcome))
+            .map(|b| &b.tx)
+    }
+}
+
+// ── WASM bindings ─────────────────────────────────────────────────────
+
+/// Produce a signed oracle attestation (JSON) for `outcome`.
+#[wasm_bindgen(js_name = "oracleAttest")]
+pub fn oracle_attest(oracle: &CdiWallet, outcome: u64) -> String {
+    serde_json::to_string(&attest(oracle, outcome)).unwrap_or_default()
+}
+
+/// Cover an outcome interval with digit prefixes, returned as JSON.
+#[wasm_bindgen(js_name = "coverInterval")]
+pub fn cover_interval_js(start: u64, end: u64, base: u8, num_digits: u8) -> String {
+    serde_json::to_string(&cover_interval(start, end, base, num_digits)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_to_digits() {
+        assert_eq!(value_to_digits(0, 2, 4), vec![0, 0, 0, 0]);
+        assert_eq!(value_to_digits(5, 2, 4), vec![0, 1, 0, 1]);
+        assert_eq!(value_to_digits(123, 10, 3), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_full_domain_collapses_to_empty_prefix() {
+        let cover = cover_interval(0, 15, 2, 4);
+        assert_eq!(cover.len(), 1);
+        assert!(cover[0].digits.is_empty());
+    }
+
+    #[test]
+    fn test_cover_is_minimal_and_exact() {
+        // [2, 13] in base 2, 4 digits. Every value in range matches exactly one
+        // prefix; no value outside the range matches any.
+        let start = 2;
+        let end = 13;
+        let cover = cover_interval(start, end, 2, 4);
+        for v in 0u64..16 {
+            let hits = cover.iter().filter(|p| p.matches(v)).count();
+            if v >= start && v <= end {
+                assert_eq!(hits, 1, "value {} should match exactly one prefix", v);
+            } else {
+                assert_eq!(hits, 0, "value {} should match no prefix", v);
+            }
+        }
+        // O(n·b), not O(b^n): far fewer than the 12 leaves covered.
+        assert!(cover.len() < 12);
+    }
+
+    #[test]
+    fn test_single_point_interval() {
+        let cover = cover_interval(7, 7, 2, 4);
+        assert_eq!(cover.len(), 1);
+        assert_eq!(cover[0].digits, vec![0, 1, 1, 1]);
+        assert!(cover[0].matches(7));
+        assert!(!cover[0].matches(6));
+    }
+
+    #[test]
+    fn test_attestation_roundtrip() {
+        let oracle = CdiWallet::generate();
+        let att = attest(&oracle, 42);
+        assert!(att.verify());
+        let mut tampered = att.clone();
+        tampered.outcome = 43;
+        assert!(!tampered.verify());
+    }
+
+    #[test]
+    fn test_contract_settles_matching_branch() {
+        let oracle = CdiWallet::generate();
+        let payer = CdiWallet::generate();
+        let high = crate::signing::sign_transaction_core(&payer, "provider", 100, "inference_fee", 1.0);
+        let low = crate::signing::sign_transaction_core(&payer, "provider", 10, "inference_fee", 1.0);
+        let high_tx: SignedTransaction = serde_json::from_str(&high).unwrap();
+        let low_tx: SignedTransaction = serde_json::from_str(&low).unwrap();
+
+        // Score in [8, 15] pays `high`, [0, 7] pays `low`.
+        let contract = OracleContract::from_curve(
+            &oracle.get_public_key_hex(),
+            2,
+            4,
+            &[(8, 15, high_tx.clone()), (0, 7, low_tx.clone())],
+        );
+
+        let att_high = attest(&oracle, 12);
+        assert_eq!(contract.settle(&att_high).unwrap().amount, high_tx.amount);
+        let att_low = attest(&oracle, 3);
+        assert_eq!(contract.settle(&att_low).unwrap().amount, low_tx.amount);
+    }
+
+    #[test]
+    fn test_contract_rejects_wrong_oracle() {
+        let oracle = CdiWallet::generate();
+        let imposter = CdiWallet::generate();
+        let payer = CdiWallet::generate();
+        let tx: SignedTransaction = serde_json::from_str(
+            &crate::signing::sign_transaction_core(&payer, "provider", 100, "inference_fee", 1.0),
+        ).unwrap();
+        let contract = OracleContract::from_curve(&oracle.get_public_key_hex(), 2, 4, &[(0, 15, tx)]);
+        assert!(contract.settle(&attest(&imposter, 5)).is_none());
+    }
+}
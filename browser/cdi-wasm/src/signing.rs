@@ -16,7 +16,7 @@ pub struct SignedTransaction {
     pub from: String,          // peerId of sender
     pub pub_key: String,       // hex public key (for verification)
     pub to: String,            // peerId of recipient
-    pub amount: f64,           // CDI amount
+    pub amount: u64,           // CDI amount in base units
     pub tx_type: String,       // "transfer" | "inference_fee" | "shard_reward" | "royalty"
     pub timestamp: f64,        // Unix epoch ms
     pub signature: String,     // hex Ed25519 signature
@@ -27,19 +27,48 @@ pub struct SignedTransaction {
 pub struct TransactionData {
     pub from: String,
     pub to: String,
-    pub amount: f64,
+    pub amount: u64,
     pub tx_type: String,
     pub timestamp: f64,
 }
 
 impl TransactionData {
-    /// Create canonical bytes for signing (deterministic).
+    /// Canonical binary serialization — the form a transaction is actually
+    /// signed over (see [`TransactionData::signing_bytes`]).
+    ///
+    /// Length-prefixed, fixed field order, with the amount as its integer
+    /// base-unit value. Peer-ids and public keys are stored as compact byte
+    /// arrays rather than hex, so a constrained wallet has less to parse and
+    /// display. The JSON form is kept as an interop option only.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_field(&mut buf, &self.from);
+        write_field(&mut buf, &self.to);
+        buf.extend_from_slice(&self.amount.to_le_bytes());
+        write_field(&mut buf, &self.tx_type);
+        buf.extend_from_slice(&(self.timestamp as u64).to_le_bytes());
+        buf
+    }
+
+    /// Parse the canonical binary form produced by [`TransactionData::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<TransactionData, String> {
+        let mut r = ByteReader::new(bytes);
+        let from = r.read_field()?;
+        let to = r.read_field()?;
+        let amount = r.read_u64()?;
+        let tx_type = r.read_field()?;
+        let timestamp = r.read_u64()? as f64;
+        Ok(TransactionData { from, to, amount, tx_type, timestamp })
+    }
+
+    /// Bytes that are actually signed: SHA-256 of the canonical binary form.
+    ///
+    /// Hashing the compact binary (rather than a colon-joined UTF-8 string)
+    /// makes the signed payload independent of JSON/float formatting.
     pub fn signing_bytes(&self) -> Vec<u8> {
-        let canonical = format!(
-            "{}:{}:{}:{}:{}",
-            self.from, self.to, self.amount, self.tx_type, self.timestamp as u64
-        );
-        canonical.into_bytes()
+        let mut hasher = Sha256::new();
+        hasher.update(self.to_bytes());
+        hasher.finalize().to_vec()
     }
 
     /// Derive transaction ID: hex(SHA-256(signing_bytes))
@@ -50,8 +79,102 @@ impl TransactionData {
     }
 }
 
+impl SignedTransaction {
+    /// Canonical binary serialization of the full signed envelope.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_field(&mut buf, &self.tx_id);
+        write_field(&mut buf, &self.from);
+        write_field(&mut buf, &self.pub_key);
+        write_field(&mut buf, &self.to);
+        buf.extend_from_slice(&self.amount.to_le_bytes());
+        write_field(&mut buf, &self.tx_type);
+        buf.extend_from_slice(&(self.timestamp as u64).to_le_bytes());
+        write_field(&mut buf, &self.signature);
+        buf
+    }
+
+    /// Parse the canonical binary form produced by [`SignedTransaction::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<SignedTransaction, String> {
+        let mut r = ByteReader::new(bytes);
+        let tx_id = r.read_field()?;
+        let from = r.read_field()?;
+        let pub_key = r.read_field()?;
+        let to = r.read_field()?;
+        let amount = r.read_u64()?;
+        let tx_type = r.read_field()?;
+        let timestamp = r.read_u64()? as f64;
+        let signature = r.read_field()?;
+        Ok(SignedTransaction { tx_id, from, pub_key, to, amount, tx_type, timestamp, signature })
+    }
+}
+
+// ── Canonical binary encoding helpers ─────────────────────────────────
+
+/// Write a string field: a 1-byte encoding tag, a u32 length, then the bytes.
+/// Even-length lowercase-hex strings (peer-ids, keys, signatures) are stored
+/// decoded so a 64-char hex id shrinks to 32 bytes; anything else is stored as
+/// UTF-8. A u32 length avoids silently truncating a field past `u16::MAX`.
+fn write_field(buf: &mut Vec<u8>, value: &str) {
+    let (tag, bytes) = match hex_decode_even(value) {
+        Some(b) => (1u8, b),
+        None => (0u8, value.as_bytes().to_vec()),
+    };
+    buf.push(tag);
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&bytes);
+}
+
+/// Decode `s` as hex iff it is non-empty, even-length, and all *lowercase* hex
+/// digits. Restricting to lowercase keeps the binary form byte-for-byte
+/// reversible (`hex::encode` emits lowercase), so an uppercase-hex string
+/// round-trips unchanged through the UTF-8 path instead.
+fn hex_decode_even(s: &str) -> Option<Vec<u8>> {
+    let is_lower_hex = |b: u8| b.is_ascii_digit() || (b'a'..=b'f').contains(&b);
+    if s.is_empty() || s.len() % 2 != 0 || !s.bytes().all(is_lower_hex) {
+        return None;
+    }
+    hex::decode(s).ok()
+}
+
+/// Cursor over a canonical binary buffer.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.pos + n > self.bytes.len() {
+            return Err("unexpected end of buffer".to_string());
+        }
+        let slice = &self.bytes[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn read_field(&mut self) -> Result<String, String> {
+        let tag = self.take(1)?[0];
+        let len = u32::from_le_bytes(self.take(4)?.try_into().unwrap()) as usize;
+        let bytes = self.take(len)?;
+        match tag {
+            1 => Ok(hex::encode(bytes)),
+            0 => String::from_utf8(bytes.to_vec()).map_err(|_| "invalid UTF-8 field".to_string()),
+            _ => Err("unknown field encoding tag".to_string()),
+        }
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
 /// Sign a transaction (core, works on all targets).
-pub fn sign_transaction_core(wallet: &CdiWallet, to: &str, amount: f64, tx_type: &str, timestamp: f64) -> String {
+pub fn sign_transaction_core(wallet: &CdiWallet, to: &str, amount: u64, tx_type: &str, timestamp: f64) -> String {
     let tx_data = TransactionData {
         from: wallet.get_peer_id().to_string(),
         to: to.to_string(),
@@ -109,7 +232,7 @@ pub fn verify_transaction_core(signed_tx_json: &str) -> bool {
 // ── WASM bindings ─────────────────────────────────────────────────────
 
 #[wasm_bindgen(js_name = "signTransaction")]
-pub fn sign_transaction(wallet: &CdiWallet, to: &str, amount: f64, tx_type: &str, timestamp: f64) -> String {
+pub fn sign_transaction(wallet: &CdiWallet, to: &str, amount: u64, tx_type: &str, timestamp: f64) -> String {
     sign_transaction_core(wallet, to, amount, tx_type, timestamp)
 }
 
@@ -118,6 +241,22 @@ pub fn verify_transaction(signed_tx_json: &str) -> bool {
     verify_transaction_core(signed_tx_json)
 }
 
+/// Encode a signed transaction (JSON) into its canonical binary form — the
+/// compact payload a hardware-constrained signer parses and displays.
+#[wasm_bindgen(js_name = "transactionToBytes")]
+pub fn transaction_to_bytes(signed_tx_json: &str) -> Result<Vec<u8>, JsValue> {
+    let signed: SignedTransaction = serde_json::from_str(signed_tx_json)
+        .map_err(|e| JsValue::from_str(&format!("invalid transaction JSON: {}", e)))?;
+    Ok(signed.to_bytes())
+}
+
+/// Decode a canonical binary signed transaction back into JSON.
+#[wasm_bindgen(js_name = "transactionFromBytes")]
+pub fn transaction_from_bytes(bytes: &[u8]) -> Result<String, JsValue> {
+    let signed = SignedTransaction::from_bytes(bytes).map_err(|e| JsValue::from_str(&e))?;
+    Ok(serde_json::to_string(&signed).unwrap_or_default())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,15 +265,15 @@ mod tests {
     #[test]
     fn test_sign_and_verify_transaction() {
         let wallet = CdiWallet::generate();
-        let json = sign_transaction_core(&wallet, "recipient_peer_id", 10.5, "transfer", 1700000000000.0);
+        let json = sign_transaction_core(&wallet, "recipient_peer_id", 1_050_000_000, "transfer", 1700000000000.0);
         assert!(verify_transaction_core(&json));
     }
 
     #[test]
     fn test_tampered_transaction_fails() {
         let wallet = CdiWallet::generate();
-        let json = sign_transaction_core(&wallet, "recipient", 10.0, "transfer", 1700000000000.0);
-        let tampered = json.replace("10.0", "999.0");
+        let json = sign_transaction_core(&wallet, "recipient", 1_000_000_000, "transfer", 1700000000000.0);
+        let tampered = json.replace("1000000000", "999000000000");
         assert!(!verify_transaction_core(&tampered));
     }
 
@@ -143,7 +282,7 @@ mod tests {
         let tx = TransactionData {
             from: "alice".into(),
             to: "bob".into(),
-            amount: 50.0,
+            amount: 5_000_000_000,
             tx_type: "transfer".into(),
             timestamp: 1700000000000.0,
         };
@@ -157,7 +296,7 @@ mod tests {
     fn test_different_tx_types() {
         let wallet = CdiWallet::generate();
         for tx_type in &["transfer", "inference_fee", "shard_reward", "royalty"] {
-            let json = sign_transaction_core(&wallet, "peer", 1.0, tx_type, 1700000000000.0);
+            let json = sign_transaction_core(&wallet, "peer", 100_000_000, tx_type, 1700000000000.0);
             assert!(verify_transaction_core(&json), "Failed for tx_type: {}", tx_type);
         }
     }
@@ -167,4 +306,62 @@ mod tests {
         assert!(!verify_transaction_core("not json"));
         assert!(!verify_transaction_core("{}"));
     }
+
+    #[test]
+    fn test_transaction_data_binary_roundtrip() {
+        let tx = TransactionData {
+            from: "a".repeat(64), // a realistic 32-byte hex peer-id
+            to: "recipient_peer_id".into(),
+            amount: 1_050_000_000,
+            tx_type: "transfer".into(),
+            timestamp: 1700000000000.0,
+        };
+        let bytes = tx.to_bytes();
+        let back = TransactionData::from_bytes(&bytes).unwrap();
+        assert_eq!(back.from, tx.from);
+        assert_eq!(back.to, tx.to);
+        assert_eq!(back.amount, tx.amount);
+        assert_eq!(back.tx_type, tx.tx_type);
+        assert_eq!(back.timestamp, tx.timestamp);
+    }
+
+    #[test]
+    fn test_signed_transaction_binary_roundtrip() {
+        let wallet = CdiWallet::generate();
+        let json = sign_transaction_core(&wallet, "provider", 5_000_000_000, "inference_fee", 1700000000000.0);
+        let signed: SignedTransaction = serde_json::from_str(&json).unwrap();
+        let bytes = signed.to_bytes();
+        let back = SignedTransaction::from_bytes(&bytes).unwrap();
+        assert_eq!(back.tx_id, signed.tx_id);
+        assert_eq!(back.signature, signed.signature);
+        // The binary envelope is substantially smaller than the JSON blob.
+        assert!(bytes.len() < json.len());
+        // And still verifies.
+        assert!(verify_transaction_core(&serde_json::to_string(&back).unwrap()));
+    }
+
+    #[test]
+    fn test_uppercase_hex_field_roundtrips() {
+        // An uppercase-hex peer-id must survive to_bytes/from_bytes unchanged,
+        // since signing_bytes now depends on the binary form.
+        let tx = TransactionData {
+            from: "ABCDEF0123456789".into(),
+            to: "deadbeef".into(),
+            amount: 1,
+            tx_type: "transfer".into(),
+            timestamp: 1.0,
+        };
+        let back = TransactionData::from_bytes(&tx.to_bytes()).unwrap();
+        assert_eq!(back.from, tx.from);
+        assert_eq!(back.to, tx.to);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_truncated() {
+        let wallet = CdiWallet::generate();
+        let json = sign_transaction_core(&wallet, "provider", 1, "transfer", 1.0);
+        let signed: SignedTransaction = serde_json::from_str(&json).unwrap();
+        let bytes = signed.to_bytes();
+        assert!(SignedTransaction::from_bytes(&bytes[..bytes.len() - 4]).is_err());
+    }
 }
@@ -1,33 +1,56 @@
 //! CDI Tokenomics — fee calculation, reward splits, halving schedule.
 //!
 //! Mirrors the Node.js TokenLedger logic but runs in WASM for browser nodes.
-//! All constants match the whitepaper exactly.
+//! All constants match the whitepaper exactly. Every amount is an integer
+//! count of base units (see [`crate::amount`]); splits provably sum to their
+//! input with any remainder assigned deterministically to the provider.
 
+use serde::{Serialize, Deserialize};
 use wasm_bindgen::prelude::*;
 
+use crate::amount::BASE_UNITS_PER_CDI;
+
 // ── Constants (match whitepaper) ──────────────────────────────────────
-pub const MAX_SUPPLY: f64 = 21_000_000.0;
-pub const GENESIS_BLOCK_REWARD: f64 = 50.0;
-pub const PROVIDER_SHARE: f64 = 0.85;
-pub const CREATOR_SHARE: f64 = 0.09;  // 60% of 15%
-pub const IMPROVER_SHARE: f64 = 0.06; // 40% of 15%
-pub const IMPROVER_DECAY: f64 = 0.70; // 70% decay per depth level
+pub const MAX_SUPPLY_UNITS: u64 = 21_000_000 * BASE_UNITS_PER_CDI;
+pub const GENESIS_BLOCK_REWARD_UNITS: u64 = 50 * BASE_UNITS_PER_CDI;
+pub const PROVIDER_BPS: u64 = 8_500; // 85%
+pub const CREATOR_BPS: u64 = 900;    // 9% = 60% of 15%
+pub const IMPROVER_BPS: u64 = 600;   // 6% = 40% of 15%
+pub const IMPROVER_DECAY_NUM: u64 = 70; // each depth level keeps 70% of the previous
+pub const IMPROVER_DECAY_DEN: u64 = 100;
 
 // ── Fee Split ─────────────────────────────────────────────────────────
 
+/// A fee split over integer base units. Always satisfies
+/// `provider + creator + improver == total`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub provider: u64,
+    pub creator: u64,
+    pub improver: u64,
+    pub total: u64,
+}
+
 /// Split an inference fee into provider, creator, and improver portions.
-#[wasm_bindgen(js_name = "splitFee")]
-pub fn split_fee(total_fee: f64) -> JsValue {
-    let provider = total_fee * PROVIDER_SHARE;
-    let creator = total_fee * CREATOR_SHARE;
-    let improver = total_fee * IMPROVER_SHARE;
+///
+/// Creator and improver take floored basis-point shares; the provider absorbs
+/// the floor remainder so the three parts sum to exactly `total`.
+pub fn split_fee_units(total: u64) -> FeeSplit {
+    let creator = total * CREATOR_BPS / 10_000;
+    let improver = total * IMPROVER_BPS / 10_000;
+    let provider = total - creator - improver;
+    FeeSplit { provider, creator, improver, total }
+}
 
-    // Return as JS object
+/// Split an inference fee into provider, creator, and improver portions.
+#[wasm_bindgen(js_name = "splitFee")]
+pub fn split_fee(total_fee: u64) -> JsValue {
+    let s = split_fee_units(total_fee);
     let obj = js_sys::Object::new();
-    js_sys::Reflect::set(&obj, &"provider".into(), &JsValue::from_f64(provider)).ok();
-    js_sys::Reflect::set(&obj, &"creator".into(), &JsValue::from_f64(creator)).ok();
-    js_sys::Reflect::set(&obj, &"improver".into(), &JsValue::from_f64(improver)).ok();
-    js_sys::Reflect::set(&obj, &"total".into(), &JsValue::from_f64(total_fee)).ok();
+    js_sys::Reflect::set(&obj, &"provider".into(), &js_sys::BigInt::from(s.provider).into()).ok();
+    js_sys::Reflect::set(&obj, &"creator".into(), &js_sys::BigInt::from(s.creator).into()).ok();
+    js_sys::Reflect::set(&obj, &"improver".into(), &js_sys::BigInt::from(s.improver).into()).ok();
+    js_sys::Reflect::set(&obj, &"total".into(), &js_sys::BigInt::from(s.total).into()).ok();
     obj.into()
 }
 
@@ -36,22 +59,27 @@ pub fn split_fee(total_fee: f64) -> JsValue {
 /// Calculate reward per shard for a distributed inference.
 /// Each shard gets a proportional share of the provider fee based on compute weight.
 #[wasm_bindgen(js_name = "shardReward")]
-pub fn shard_reward(total_fee: f64, shard_compute_weight: f64, total_compute_weight: f64) -> f64 {
-    if total_compute_weight <= 0.0 {
-        return 0.0;
+pub fn shard_reward(total_fee: u64, shard_compute_weight: u64, total_compute_weight: u64) -> u64 {
+    if total_compute_weight == 0 {
+        return 0;
     }
-    let provider_pool = total_fee * PROVIDER_SHARE;
-    provider_pool * (shard_compute_weight / total_compute_weight)
+    let provider_pool = split_fee_units(total_fee).provider;
+    // u128 intermediate so the weight product can't overflow.
+    ((provider_pool as u128 * shard_compute_weight as u128) / total_compute_weight as u128) as u64
 }
 
 // ── Halving Schedule ──────────────────────────────────────────────────
 
 /// Calculate block reward for a given epoch.
-/// reward(epoch) = genesis_reward / 2^epoch
+/// reward(epoch) = genesis_reward / 2^epoch, floored at 1 base unit.
 #[wasm_bindgen(js_name = "blockReward")]
-pub fn block_reward(epoch: u32) -> f64 {
-    let reward = GENESIS_BLOCK_REWARD / (2.0_f64.powi(epoch as i32));
-    if reward < 1e-8 { 1e-8 } else { reward }  // minimum 1 satoshi CDI
+pub fn block_reward(epoch: u32) -> u64 {
+    let reward = if epoch >= 64 {
+        0
+    } else {
+        GENESIS_BLOCK_REWARD_UNITS >> epoch
+    };
+    if reward < 1 { 1 } else { reward } // minimum 1 base unit
 }
 
 /// Calculate the epoch number based on total inferences processed.
@@ -69,11 +97,99 @@ pub fn current_epoch(total_inferences: u64, ips: f64, epoch_duration_secs: f64)
 // ── Improver Royalty Cascade ──────────────────────────────────────────
 
 /// Calculate improver royalty at a given depth level.
-/// royalty(depth) = improver_pool * decay^depth
+/// royalty(depth) = improver_pool * decay^depth, applied as a repeated
+/// integer multiply-then-divide that floors at each depth level.
 #[wasm_bindgen(js_name = "improverRoyaltyAtDepth")]
-pub fn improver_royalty_at_depth(total_fee: f64, depth: u32) -> f64 {
-    let improver_pool = total_fee * IMPROVER_SHARE;
-    improver_pool * IMPROVER_DECAY.powi(depth as i32)
+pub fn improver_royalty_at_depth(total_fee: u64, depth: u32) -> u64 {
+    let mut royalty = split_fee_units(total_fee).improver;
+    for _ in 0..depth {
+        royalty = royalty * IMPROVER_DECAY_NUM / IMPROVER_DECAY_DEN;
+    }
+    royalty
+}
+
+// ── Validator Staking & Reward Distribution ───────────────────────────
+
+/// A validator candidate and the amount of CDI (base units) it has staked.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Validator {
+    pub peer_id: String,
+    pub stake: u64,
+}
+
+/// Select the active validator set, hard-limited to `slots`.
+///
+/// Candidates are ranked by stake (descending), breaking ties deterministically
+/// by `peer_id` (ascending), then truncated to the slot count — so the set is
+/// bounded even when more candidates stake than there are slots.
+pub fn select_validators(stakes: &[Validator], slots: usize) -> Vec<Validator> {
+    let mut ranked: Vec<Validator> = stakes.iter().filter(|v| v.stake > 0).cloned().collect();
+    ranked.sort_by(|a, b| b.stake.cmp(&a.stake).then_with(|| a.peer_id.cmp(&b.peer_id)));
+    ranked.truncate(slots);
+    ranked
+}
+
+/// A single validator's floored share of the epoch reward:
+/// `block_reward(epoch) * stake / total_active_stake`.
+///
+/// This is the floor only; the floor remainder is redistributed by
+/// [`distribute_block_reward`] so the active set's shares sum to exactly the
+/// minted amount.
+pub fn validator_reward(epoch: u32, stake: u64, total_active_stake: u64) -> u64 {
+    if total_active_stake == 0 {
+        return 0;
+    }
+    let minted = block_reward(epoch) as u128;
+    ((minted * stake as u128) / total_active_stake as u128) as u64
+}
+
+/// Distribute an epoch's `block_reward` across the active set proportionally to
+/// stake, assigning the floor remainder deterministically so the shares sum to
+/// exactly `block_reward(epoch)` — reward accrual never exceeds the minted
+/// amount at the halving/rounding boundary. An empty active set, or one whose
+/// total stake is zero, is assigned nothing.
+pub fn distribute_block_reward(epoch: u32, active: &[Validator]) -> Vec<(String, u64)> {
+    let minted = block_reward(epoch);
+    let total_stake: u64 = active.iter().map(|v| v.stake).sum();
+    if total_stake == 0 || active.is_empty() {
+        return active.iter().map(|v| (v.peer_id.clone(), 0)).collect();
+    }
+
+    let mut dist: Vec<(String, u64)> = active
+        .iter()
+        .map(|v| (v.peer_id.clone(), validator_reward(epoch, v.stake, total_stake)))
+        .collect();
+
+    // Hand the floor remainder out one unit at a time, in the same deterministic
+    // order the active set is ranked (stake desc, peer-id asc).
+    let assigned: u64 = dist.iter().map(|(_, r)| *r).sum();
+    let mut remainder = minted - assigned;
+    let mut order: Vec<usize> = (0..active.len()).collect();
+    order.sort_by(|&i, &j| {
+        active[j].stake.cmp(&active[i].stake).then_with(|| active[i].peer_id.cmp(&active[j].peer_id))
+    });
+    let mut k = 0;
+    while remainder > 0 {
+        let idx = order[k % order.len()];
+        dist[idx].1 += 1;
+        remainder -= 1;
+        k += 1;
+    }
+    dist
+}
+
+/// Verify that a reward distribution sums to exactly `block_reward(epoch)`.
+pub fn distribution_sums_correctly(epoch: u32, dist: &[(String, u64)]) -> bool {
+    dist.iter().map(|(_, r)| *r).sum::<u64>() == block_reward(epoch)
+}
+
+/// Select the active set and distribute the epoch reward to it, as JSON.
+#[wasm_bindgen(js_name = "distributeBlockReward")]
+pub fn distribute_block_reward_js(epoch: u32, stakes_json: &str, max_validator_slots: usize) -> String {
+    let stakes: Vec<Validator> = serde_json::from_str(stakes_json).unwrap_or_default();
+    let active = select_validators(&stakes, max_validator_slots);
+    let dist = distribute_block_reward(epoch, &active);
+    serde_json::to_string(&dist).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -82,49 +198,56 @@ mod tests {
 
     #[test]
     fn test_fee_split_sums_to_total() {
-        let fee = 100.0;
-        let provider = fee * PROVIDER_SHARE;
-        let creator = fee * CREATOR_SHARE;
-        let improver = fee * IMPROVER_SHARE;
-        let sum = provider + creator + improver;
-        assert!((sum - fee).abs() < 1e-10, "fee split doesn't sum: {}", sum);
+        // Exact for any input, including those not divisible by the shares.
+        for &fee in &[0u64, 1, 7, 100, 100_000_000, 123_456_789] {
+            let s = split_fee_units(fee);
+            assert_eq!(s.provider + s.creator + s.improver, fee, "fee {} didn't sum", fee);
+        }
     }
 
     #[test]
     fn test_fee_split_ratios() {
-        let fee = 100.0;
-        assert!((fee * PROVIDER_SHARE - 85.0).abs() < 1e-10);
-        assert!((fee * CREATOR_SHARE - 9.0).abs() < 1e-10);
-        assert!((fee * IMPROVER_SHARE - 6.0).abs() < 1e-10);
+        let fee = 100 * BASE_UNITS_PER_CDI;
+        let s = split_fee_units(fee);
+        assert_eq!(s.provider, 85 * BASE_UNITS_PER_CDI);
+        assert_eq!(s.creator, 9 * BASE_UNITS_PER_CDI);
+        assert_eq!(s.improver, 6 * BASE_UNITS_PER_CDI);
+    }
+
+    #[test]
+    fn test_fee_split_remainder_to_provider() {
+        // 7 base units: creator/improver floor to 0, provider keeps the rest.
+        let s = split_fee_units(7);
+        assert_eq!(s.creator, 0);
+        assert_eq!(s.improver, 0);
+        assert_eq!(s.provider, 7);
     }
 
     #[test]
     fn test_shard_reward_proportional() {
-        // 2 shards with equal weight → each gets half the provider pool
-        let fee = 100.0;
-        let r = shard_reward(fee, 1.0, 2.0);
-        let expected = 85.0 / 2.0;
-        assert!((r - expected).abs() < 1e-10);
+        // 2 shards with equal weight → each gets half the provider pool.
+        let fee = 100 * BASE_UNITS_PER_CDI;
+        let r = shard_reward(fee, 1, 2);
+        assert_eq!(r, 85 * BASE_UNITS_PER_CDI / 2);
     }
 
     #[test]
     fn test_shard_reward_zero_weight() {
-        assert_eq!(shard_reward(100.0, 1.0, 0.0), 0.0);
+        assert_eq!(shard_reward(100 * BASE_UNITS_PER_CDI, 1, 0), 0);
     }
 
     #[test]
     fn test_block_reward_halving() {
-        assert!((block_reward(0) - 50.0).abs() < 1e-10);
-        assert!((block_reward(1) - 25.0).abs() < 1e-10);
-        assert!((block_reward(2) - 12.5).abs() < 1e-10);
-        assert!((block_reward(10) - 50.0 / 1024.0).abs() < 1e-10);
+        assert_eq!(block_reward(0), 50 * BASE_UNITS_PER_CDI);
+        assert_eq!(block_reward(1), 25 * BASE_UNITS_PER_CDI);
+        assert_eq!(block_reward(2), 125 * BASE_UNITS_PER_CDI / 10);
+        assert_eq!(block_reward(10), 50 * BASE_UNITS_PER_CDI / 1024);
     }
 
     #[test]
     fn test_block_reward_minimum() {
-        // After many halvings, reward floors at 1e-8
-        let r = block_reward(100);
-        assert_eq!(r, 1e-8);
+        // After many halvings, reward floors at 1 base unit.
+        assert_eq!(block_reward(100), 1);
     }
 
     #[test]
@@ -138,18 +261,78 @@ mod tests {
 
     #[test]
     fn test_improver_royalty_decay() {
-        let fee = 100.0;
-        let d0 = improver_royalty_at_depth(fee, 0);
-        let d1 = improver_royalty_at_depth(fee, 1);
-        let d2 = improver_royalty_at_depth(fee, 2);
-
-        assert!((d0 - 6.0).abs() < 1e-10);           // 6% at depth 0
-        assert!((d1 - 6.0 * 0.7).abs() < 1e-10);     // 4.2% at depth 1
-        assert!((d2 - 6.0 * 0.49).abs() < 1e-10);    // 2.94% at depth 2
+        let fee = 100 * BASE_UNITS_PER_CDI;
+        let pool = 6 * BASE_UNITS_PER_CDI;
+        assert_eq!(improver_royalty_at_depth(fee, 0), pool);            // 6% at depth 0
+        assert_eq!(improver_royalty_at_depth(fee, 1), pool * 70 / 100); // 4.2% at depth 1
+        assert_eq!(improver_royalty_at_depth(fee, 2), pool * 70 / 100 * 70 / 100);
     }
 
     #[test]
     fn test_max_supply_constant() {
-        assert_eq!(MAX_SUPPLY, 21_000_000.0);
+        assert_eq!(MAX_SUPPLY_UNITS, 2_100_000_000_000_000);
+    }
+
+    fn validators(pairs: &[(&str, u64)]) -> Vec<Validator> {
+        pairs.iter().map(|(p, s)| Validator { peer_id: (*p).into(), stake: *s }).collect()
+    }
+
+    #[test]
+    fn test_select_validators_caps_active_set() {
+        let candidates = validators(&[("a", 30), ("b", 50), ("c", 10), ("d", 40)]);
+        let active = select_validators(&candidates, 2);
+        // Hard-limited to the slot count, highest stakes first.
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].peer_id, "b"); // 50
+        assert_eq!(active[1].peer_id, "d"); // 40
+    }
+
+    #[test]
+    fn test_select_validators_tie_break_by_peer_id() {
+        let candidates = validators(&[("z", 100), ("a", 100), ("m", 100)]);
+        let active = select_validators(&candidates, 2);
+        // Equal stake → ascending peer-id decides.
+        assert_eq!(active[0].peer_id, "a");
+        assert_eq!(active[1].peer_id, "m");
+    }
+
+    #[test]
+    fn test_distribution_sums_to_minted() {
+        // Stakes that don't divide the reward evenly still sum exactly.
+        let active = validators(&[("a", 1), ("b", 1), ("c", 1)]);
+        let dist = distribute_block_reward(0, &active);
+        assert!(distribution_sums_correctly(0, &dist));
+        let total: u64 = dist.iter().map(|(_, r)| *r).sum();
+        assert_eq!(total, block_reward(0));
+    }
+
+    #[test]
+    fn test_distribution_never_exceeds_minted_at_boundary() {
+        // At the floor epoch only 1 base unit is minted; it must not be
+        // over-assigned across many validators.
+        let active = validators(&[("a", 5), ("b", 3), ("c", 2)]);
+        let dist = distribute_block_reward(100, &active);
+        let total: u64 = dist.iter().map(|(_, r)| *r).sum();
+        assert_eq!(total, block_reward(100));
+        assert_eq!(total, 1);
+    }
+
+    #[test]
+    fn test_distribution_proportional_to_stake() {
+        let active = validators(&[("a", 75), ("b", 25)]);
+        let dist = distribute_block_reward(0, &active);
+        let a = dist.iter().find(|(p, _)| p == "a").unwrap().1;
+        let b = dist.iter().find(|(p, _)| p == "b").unwrap().1;
+        assert_eq!(a, block_reward(0) * 75 / 100);
+        assert!(a > b);
+        assert!(distribution_sums_correctly(0, &dist));
+    }
+
+    #[test]
+    fn test_distribution_empty_or_zero_stake() {
+        assert!(distribute_block_reward(0, &[]).is_empty());
+        let active = validators(&[("a", 0), ("b", 0)]);
+        let dist = distribute_block_reward(0, &active);
+        assert!(dist.iter().all(|(_, r)| *r == 0));
     }
 }
@@ -4,11 +4,13 @@
 //! PeerId = hex(SHA-256(raw_public_key_32_bytes))
 
 use ed25519_dalek::{SigningKey, VerifyingKey, Signer, Verifier, Signature};
-use sha2::{Sha256, Digest};
+use sha2::{Sha256, Sha512, Digest};
 use rand::rngs::OsRng;
 use serde::{Serialize, Deserialize};
 use wasm_bindgen::prelude::*;
 
+use curve25519_dalek::{EdwardsPoint, Scalar, edwards::CompressedEdwardsY};
+
 /// Serializable wallet data for localStorage persistence.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct WalletData {
@@ -113,6 +115,163 @@ impl CdiWallet {
         hasher.update(vk.as_bytes());
         hex::encode(hasher.finalize())
     }
+
+    /// Produce an adaptor pre-signature over `data`, locked to the adaptor
+    /// point `T = t·G` supplied (hex-encoded) by the counterparty.
+    ///
+    /// Schnorr over the Ed25519 basepoint: we draw a deterministic nonce `r`,
+    /// form `R' = r·G + T`, challenge `e = H(R' ‖ P ‖ m)`, and emit the
+    /// pre-signature `s' = r + e·x`. `(R', s')` is *not* a valid signature —
+    /// only the holder of `t` can complete it (see
+    /// [`CdiWallet::complete_adaptor`]).
+    pub fn pre_sign(&self, data: &[u8], adaptor_point_hex: &str) -> Result<AdaptorPreSignature, String> {
+        let t_point = decode_point(adaptor_point_hex)?;
+        let x = self.secret_scalar();
+        let p = EdwardsPoint::mul_base(&x);
+        let r = self.nonce_scalar(data, &t_point);
+        let r_prime = EdwardsPoint::mul_base(&r) + t_point;
+        let e = schnorr_challenge(&r_prime, &p, data);
+        let s_prime = r + e * x;
+        Ok(AdaptorPreSignature {
+            r_prime: hex::encode(r_prime.compress().as_bytes()),
+            s_prime: hex::encode(s_prime.to_bytes()),
+        })
+    }
+
+    /// The clamped secret scalar `x` with `P = x·G` equal to this wallet's key.
+    fn secret_scalar(&self) -> Scalar {
+        self.signing_key.to_scalar()
+    }
+
+    /// Deterministic nonce `r = H("cdi-adaptor-nonce" ‖ sk ‖ T ‖ m)`, binding
+    /// the adaptor point so re-pre-signing the same message under a different
+    /// `T` draws a fresh `r`. Without that binding a counterparty who supplies
+    /// `T` could request two pre-signatures over one message and solve for the
+    /// private key from the nonce reuse.
+    fn nonce_scalar(&self, data: &[u8], adaptor_point: &EdwardsPoint) -> Scalar {
+        let mut h = Sha512::new();
+        h.update(b"cdi-adaptor-nonce");
+        h.update(self.signing_key.to_bytes());
+        h.update(adaptor_point.compress().as_bytes());
+        h.update(data);
+        Scalar::from_hash(h)
+    }
+}
+
+/// An adaptor pre-signature `(R', s')`.
+///
+/// Invalid as a standalone signature; completed into a real Schnorr signature
+/// by adding the adaptor secret `t`, and once both signatures are public `t`
+/// can be recovered by anyone.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AdaptorPreSignature {
+    pub r_prime: String, // hex compressed Edwards point R'
+    pub s_prime: String, // hex scalar s'
+}
+
+impl CdiWallet {
+    /// Verify a pre-signature against a public key and adaptor point:
+    /// checks `s'·G == (R' − T) + e·P`.
+    pub fn verify_pre_sign(
+        public_key_hex: &str,
+        data: &[u8],
+        adaptor_point_hex: &str,
+        pre_sig: &AdaptorPreSignature,
+    ) -> bool {
+        let p = match decode_point(public_key_hex) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let t = match decode_point(adaptor_point_hex) {
+            Ok(t) => t,
+            Err(_) => return false,
+        };
+        let r_prime = match decode_point(&pre_sig.r_prime) {
+            Ok(r) => r,
+            Err(_) => return false,
+        };
+        let s_prime = match decode_scalar(&pre_sig.s_prime) {
+            Ok(s) => s,
+            Err(_) => return false,
+        };
+        let e = schnorr_challenge(&r_prime, &p, data);
+        EdwardsPoint::mul_base(&s_prime) == (r_prime - t) + e * p
+    }
+
+    /// Complete a pre-signature with the adaptor secret `t` (hex scalar),
+    /// yielding a full Schnorr signature `R' ‖ s` where `s = s' + t`.
+    pub fn complete_adaptor(pre_sig: &AdaptorPreSignature, secret_hex: &str) -> Result<String, String> {
+        let t = decode_scalar(secret_hex)?;
+        let s_prime = decode_scalar(&pre_sig.s_prime)?;
+        let r_prime = decode_point(&pre_sig.r_prime)?;
+        let s = s_prime + t;
+        let mut sig = Vec::with_capacity(64);
+        sig.extend_from_slice(r_prime.compress().as_bytes());
+        sig.extend_from_slice(&s.to_bytes());
+        Ok(hex::encode(sig))
+    }
+
+    /// Recover the adaptor secret `t = s − s'` from a pre-signature and the
+    /// matching completed signature.
+    pub fn extract_secret(pre_sig: &AdaptorPreSignature, full_sig_hex: &str) -> Result<String, String> {
+        let sig_bytes = hex::decode(full_sig_hex).map_err(|_| "invalid signature hex".to_string())?;
+        if sig_bytes.len() != 64 {
+            return Err("signature must be 64 bytes".to_string());
+        }
+        let s_bytes: [u8; 32] = sig_bytes[32..64].try_into().unwrap();
+        let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes))
+            .ok_or_else(|| "non-canonical scalar".to_string())?;
+        let s_prime = decode_scalar(&pre_sig.s_prime)?;
+        let t = s - s_prime;
+        Ok(hex::encode(t.to_bytes()))
+    }
+
+    /// Verify a completed Schnorr signature `R ‖ s` over `data`:
+    /// checks `s·G == R + e·P`.
+    pub fn verify_schnorr(public_key_hex: &str, data: &[u8], full_sig_hex: &str) -> bool {
+        let p = match decode_point(public_key_hex) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let sig_bytes = match hex::decode(full_sig_hex) {
+            Ok(b) if b.len() == 64 => b,
+            _ => return false,
+        };
+        let r = match decode_point(&hex::encode(&sig_bytes[0..32])) {
+            Ok(r) => r,
+            Err(_) => return false,
+        };
+        let s = match decode_scalar(&hex::encode(&sig_bytes[32..64])) {
+            Ok(s) => s,
+            Err(_) => return false,
+        };
+        let e = schnorr_challenge(&r, &p, data);
+        EdwardsPoint::mul_base(&s) == r + e * p
+    }
+}
+
+/// Schnorr challenge `e = H(R' ‖ P ‖ m)` reduced to a scalar.
+fn schnorr_challenge(r_prime: &EdwardsPoint, pubkey: &EdwardsPoint, msg: &[u8]) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(r_prime.compress().as_bytes());
+    h.update(pubkey.compress().as_bytes());
+    h.update(msg);
+    Scalar::from_hash(h)
+}
+
+/// Decode a hex-encoded compressed Edwards point.
+fn decode_point(hex_str: &str) -> Result<EdwardsPoint, String> {
+    let bytes = hex::decode(hex_str).map_err(|_| "invalid point hex".to_string())?;
+    let compressed = CompressedEdwardsY::from_slice(&bytes).map_err(|_| "point must be 32 bytes".to_string())?;
+    compressed.decompress().ok_or_else(|| "point not on curve".to_string())
+}
+
+/// Decode a hex-encoded canonical scalar.
+fn decode_scalar(hex_str: &str) -> Result<Scalar, String> {
+    let bytes = hex::decode(hex_str).map_err(|_| "invalid scalar hex".to_string())?;
+    let arr: [u8; 32] = bytes.try_into().map_err(|_| "scalar must be 32 bytes".to_string())?;
+    Option::<Scalar>::from(Scalar::from_canonical_bytes(arr))
+        .ok_or_else(|| "non-canonical scalar".to_string())
 }
 
 // ── WASM-specific bindings (only compiled for wasm32) ─────────────────
@@ -156,6 +315,44 @@ impl CdiWallet {
     pub fn from_json(json: &str) -> Result<CdiWallet, JsValue> {
         Self::from_json_str(json).map_err(|e| JsValue::from_str(&e))
     }
+
+    /// Pre-sign `data` under the adaptor point `T`, returning the
+    /// `AdaptorPreSignature` as JSON.
+    #[wasm_bindgen(js_name = "preSign")]
+    pub fn pre_sign_js(&self, data: &[u8], adaptor_point_hex: &str) -> Result<String, JsValue> {
+        let ps = self.pre_sign(data, adaptor_point_hex).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_json::to_string(&ps).unwrap_or_default())
+    }
+
+    #[wasm_bindgen(js_name = "verifyPreSign")]
+    pub fn verify_pre_sign_js(
+        public_key_hex: &str,
+        data: &[u8],
+        adaptor_point_hex: &str,
+        pre_sig_json: &str,
+    ) -> bool {
+        match serde_json::from_str::<AdaptorPreSignature>(pre_sig_json) {
+            Ok(ps) => Self::verify_pre_sign(public_key_hex, data, adaptor_point_hex, &ps),
+            Err(_) => false,
+        }
+    }
+
+    /// Complete a pre-signature (JSON) with the adaptor secret, returning the
+    /// hex signature.
+    #[wasm_bindgen(js_name = "completeAdaptor")]
+    pub fn complete_adaptor_js(pre_sig_json: &str, secret_hex: &str) -> Result<String, JsValue> {
+        let ps: AdaptorPreSignature = serde_json::from_str(pre_sig_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid pre-signature JSON: {}", e)))?;
+        Self::complete_adaptor(&ps, secret_hex).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Recover the adaptor secret from a pre-signature (JSON) and completed signature.
+    #[wasm_bindgen(js_name = "extractSecret")]
+    pub fn extract_secret_js(pre_sig_json: &str, full_sig_hex: &str) -> Result<String, JsValue> {
+        let ps: AdaptorPreSignature = serde_json::from_str(pre_sig_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid pre-signature JSON: {}", e)))?;
+        Self::extract_secret(&ps, full_sig_hex).map_err(|e| JsValue::from_str(&e))
+    }
 }
 
 #[cfg(test)]
@@ -226,6 +423,89 @@ mod tests {
         assert!(!w.verify_data(b"data", "abcd"));
     }
 
+    /// An adaptor point `T = t·G` and its secret `t`, for the tests below.
+    fn adaptor_pair(seed: u8) -> (Scalar, EdwardsPoint) {
+        let t = Scalar::from(seed as u64) + Scalar::from(7u64);
+        (t, EdwardsPoint::mul_base(&t))
+    }
+
+    #[test]
+    fn test_adaptor_roundtrip() {
+        let w = CdiWallet::generate();
+        let (t, t_point) = adaptor_pair(3);
+        let t_hex = hex::encode(t.to_bytes());
+        let t_point_hex = hex::encode(t_point.compress().as_bytes());
+        let msg = b"settle inference payment";
+
+        let pre = w.pre_sign(msg, &t_point_hex).unwrap();
+        assert!(CdiWallet::verify_pre_sign(&w.get_public_key_hex(), msg, &t_point_hex, &pre));
+
+        let sig = CdiWallet::complete_adaptor(&pre, &t_hex).unwrap();
+        assert!(CdiWallet::verify_schnorr(&w.get_public_key_hex(), msg, &sig));
+
+        // The counterparty recovers t once both signatures are public.
+        let recovered = CdiWallet::extract_secret(&pre, &sig).unwrap();
+        assert_eq!(recovered, t_hex);
+    }
+
+    #[test]
+    fn test_pre_signature_is_not_a_valid_signature() {
+        // The pre-signature `R' ‖ s'` on its own fails to verify, and completing
+        // it with the wrong secret yields a signature that also fails.
+        let w = CdiWallet::generate();
+        let (_t, t_point) = adaptor_pair(5);
+        let t_point_hex = hex::encode(t_point.compress().as_bytes());
+        let msg = b"conditional payout";
+        let pre = w.pre_sign(msg, &t_point_hex).unwrap();
+        let raw = format!("{}{}", pre.r_prime, pre.s_prime);
+        assert!(!CdiWallet::verify_schnorr(&w.get_public_key_hex(), msg, &raw));
+        let wrong = hex::encode(Scalar::from(99u64).to_bytes());
+        let sig = CdiWallet::complete_adaptor(&pre, &wrong).unwrap();
+        assert!(!CdiWallet::verify_schnorr(&w.get_public_key_hex(), msg, &sig));
+    }
+
+    #[test]
+    fn test_nonce_binds_adaptor_point() {
+        // Pre-signing one message under two different adaptor points must draw
+        // different nonces, so R'_1 - T1 != R'_2 - T2 and the naive key-recovery
+        // x = (s'_1 - s'_2)/(e1 - e2) yields the wrong scalar.
+        let w = CdiWallet::generate();
+        let msg = b"same message, two adaptor points";
+        let (_t1, t1) = adaptor_pair(1);
+        let (_t2, t2) = adaptor_pair(2);
+        let t1_hex = hex::encode(t1.compress().as_bytes());
+        let t2_hex = hex::encode(t2.compress().as_bytes());
+
+        let pre1 = w.pre_sign(msg, &t1_hex).unwrap();
+        let pre2 = w.pre_sign(msg, &t2_hex).unwrap();
+
+        // Recompute the public nonce points R = R' - T for each.
+        let r1 = decode_point(&pre1.r_prime).unwrap() - t1;
+        let r2 = decode_point(&pre2.r_prime).unwrap() - t2;
+        assert_ne!(r1.compress(), r2.compress(), "nonce was reused across points");
+
+        // Carry out the recovery a malicious counterparty would attempt.
+        let p = decode_point(&w.get_public_key_hex()).unwrap();
+        let e1 = schnorr_challenge(&decode_point(&pre1.r_prime).unwrap(), &p, msg);
+        let e2 = schnorr_challenge(&decode_point(&pre2.r_prime).unwrap(), &p, msg);
+        let s1 = decode_scalar(&pre1.s_prime).unwrap();
+        let s2 = decode_scalar(&pre2.s_prime).unwrap();
+        let guessed = (s1 - s2) * (e1 - e2).invert();
+        assert_ne!(guessed, w.secret_scalar(), "private key was recoverable");
+    }
+
+    #[test]
+    fn test_verify_pre_sign_rejects_wrong_point() {
+        let w = CdiWallet::generate();
+        let (_t, t_point) = adaptor_pair(2);
+        let t_point_hex = hex::encode(t_point.compress().as_bytes());
+        let msg = b"payment";
+        let pre = w.pre_sign(msg, &t_point_hex).unwrap();
+        let (_t2, other) = adaptor_pair(11);
+        let other_hex = hex::encode(other.compress().as_bytes());
+        assert!(!CdiWallet::verify_pre_sign(&w.get_public_key_hex(), msg, &other_hex, &pre));
+    }
+
     #[test]
     fn test_peer_id_is_sha256_of_pubkey() {
         let w = CdiWallet::generate();